@@ -2,56 +2,341 @@ use napi_derive::napi;
 use napi::{bindgen_prelude::*, Error, Status};
 use tokio::task;
 use rayon::prelude::*;
-use hex::encode;
+use hex::{encode, decode};
 use core::mem;
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex};
 
 // -------------- 仅导入公开可见的类型 --------------
-use kzg::eth::c_bindings::{
-    Blob, KZGProof, CKzgRet, Cell, CKZGSettings,  // 仅导入结构体和枚举（不依赖私有常量）
-};
-use kzg::eip_4844::load_trusted_setup_rust;
-use rust_kzg_blst::{
-    eip_7594::compute_cells_and_kzg_proofs,
-    types::kzg_settings::FsKZGSettings,
-};
-
-// 手动定义私有常量（EIP标准中固定，无需依赖库导出）
-const CELLS_PER_EXT_BLOB: usize = 32;  // 每个扩展Blob含32个Cell（EIP-7594）
-const BYTES_PER_G1: usize = 48;  // G1点固定48字节
+use kzg::eth::c_bindings::Blob;  // 仅导入结构体（不依赖私有常量）
+use kzg::eip_4844::{load_trusted_setup_rust, TrustedSetupError};
+use rust_kzg_blst::types::kzg_settings::FsKZGSettings;
+// 安全路径：经由DAS/Kzg4844 trait调用，代替对FsKZGSettings的裸指针转换
+use kzg_traits::{Fr as FrTrait, G1 as G1Trait, EcBackend, DAS, Kzg4844};
+use rust_kzg_blst::BlstBackend;
+
+type BlstFr = <BlstBackend as EcBackend>::Fr;
+type BlstG1 = <BlstBackend as EcBackend>::G1;
+
+// 可选的CUDA加速MSM后端：仅在启用cuda特性时编译，运行时若无可用设备则回退到CPU路径
+#[cfg(feature = "cuda")]
+use rust_kzg_blst::cuda::{cuda_device_name, compute_cells_and_kzg_proofs_cuda};
+
+// 手动定义私有常量（仅曲线本身固定的部分，无需依赖库导出）
+const BYTES_PER_G1: usize = 48;  // G1点固定48字节（BLS12-381曲线性质，与域大小无关）
+
+// -------------- 可配置的Blob/Cell维度 --------------
+// 默认对应EIP-4844/EIP-7594的标准维度，非标准DA层可通过load_trusted_setup传入自定义维度
+#[napi(object)]
+#[derive(Clone, Copy)]
+pub struct KzgSettingsConfig {
+    pub field_elements_per_blob: u32,
+    pub field_elements_per_cell: u32,
+    pub cells_per_ext_blob: u32,
+}
+
+impl Default for KzgSettingsConfig {
+    fn default() -> Self {
+        // cells_per_ext_blob必须满足 cells_per_ext_blob × field_elements_per_cell == 2 × field_elements_per_blob
+        // （见resolve_config的校验），标准EIP-4844/EIP-7594维度下为 2×4096/64 = 128，而非32
+        const FIELD_ELEMENTS_PER_BLOB: u32 = 4096;
+        const FIELD_ELEMENTS_PER_CELL: u32 = 64;
+        Self {
+            field_elements_per_blob: FIELD_ELEMENTS_PER_BLOB,
+            field_elements_per_cell: FIELD_ELEMENTS_PER_CELL,
+            cells_per_ext_blob: 2 * FIELD_ELEMENTS_PER_BLOB / FIELD_ELEMENTS_PER_CELL,
+        }
+    }
+}
+
+impl KzgSettingsConfig {
+    fn bytes_per_blob(&self) -> usize {
+        self.field_elements_per_blob as usize * 32
+    }
+
+    fn bytes_per_cell(&self) -> usize {
+        self.field_elements_per_cell as usize * 32
+    }
+
+    fn cells_per_ext_blob(&self) -> usize {
+        self.cells_per_ext_blob as usize
+    }
+}
+
+// G1 monomial点的数量即可信设置实际支持的多项式次数（= FIELD_ELEMENTS_PER_BLOB），
+// 用它校验/派生config，避免config与settings实际承载的域大小脱节
+fn derive_field_elements_per_blob(g1_monomial_bytes: &[u8]) -> usize {
+    g1_monomial_bytes.len() / BYTES_PER_G1
+}
+
+fn resolve_config(config: Option<KzgSettingsConfig>, field_elements_per_blob_actual: usize) -> Result<KzgSettingsConfig> {
+    match config {
+        None => {
+            // 未提供config时，cells_per_ext_blob必须由实际域大小代数推导，而非照抄Default的固定值，
+            // 否则非默认的实际域大小会与默认的field_elements_per_cell/cells_per_ext_blob组合脱节
+            let field_elements_per_cell = KzgSettingsConfig::default().field_elements_per_cell;
+            let cells_per_ext_blob = 2 * field_elements_per_blob_actual as u32 / field_elements_per_cell;
+            Ok(KzgSettingsConfig {
+                field_elements_per_blob: field_elements_per_blob_actual as u32,
+                field_elements_per_cell,
+                cells_per_ext_blob,
+            })
+        }
+        Some(c) => {
+            if c.field_elements_per_blob as usize != field_elements_per_blob_actual {
+                return Err(setup_error("CONFIG_DOMAIN_MISMATCH", format!(
+                    "config.field_elements_per_blob={}与可信设置实际域大小{}不一致",
+                    c.field_elements_per_blob, field_elements_per_blob_actual
+                )));
+            }
+            // 扩展域大小固定为原始域的2倍（Reed-Solomon 2x扩展），cells_per_ext_blob × field_elements_per_cell必须与之吻合
+            let extended = 2 * field_elements_per_blob_actual;
+            let declared = c.cells_per_ext_blob as usize * c.field_elements_per_cell as usize;
+            if declared != extended {
+                return Err(setup_error("CONFIG_DOMAIN_MISMATCH", format!(
+                    "cells_per_ext_blob({}) × field_elements_per_cell({}) = {}，与扩展域大小{}不一致",
+                    c.cells_per_ext_blob, c.field_elements_per_cell, declared, extended
+                )));
+            }
+            Ok(c)
+        }
+    }
+}
 
 // -------------- 辅助函数 --------------
-fn proof_to_hex(proof: &KZGProof) -> String {
-    encode(&proof.bytes)
+fn bytes_to_frs(slice: &[u8], count: usize, label: &str) -> Result<Vec<BlstFr>> {
+    slice.chunks(32).take(count)
+        .map(|chunk| BlstFr::from_bytes(chunk).map_err(|e| Error::new(Status::InvalidArg, format!(
+            "{}字段元素解析失败：{}", label, e
+        ))))
+        .collect()
+}
+
+fn blob_bytes_to_frs(blob: &Blob, field_elements_per_blob: usize) -> Result<Vec<BlstFr>> {
+    bytes_to_frs(&blob.bytes, field_elements_per_blob, "Blob")
 }
 
-fn check_c_kzg_ret(ret: CKzgRet, context: &str) -> Result<()> {
-    if ret == CKzgRet::Ok {
-        Ok(())
-    } else {
-        Err(Error::new(Status::GenericFailure, format!(
-            "{}失败：错误码 {:?}", context, ret
-        )))
+// Cell的字节即其field_elements_per_cell个字段元素的拼接，逐个解析为Fr
+fn uint8array_to_cell_frs(bytes: &Uint8Array, config: &KzgSettingsConfig) -> Result<Vec<BlstFr>> {
+    let slice = bytes.as_ref();
+    let bytes_per_cell = config.bytes_per_cell();
+    if slice.len() != bytes_per_cell {
+        return Err(Error::new(Status::InvalidArg, format!(
+            "Cell长度错误：需{}字节，实际{}字节", bytes_per_cell, slice.len()
+        )));
     }
+    bytes_to_frs(slice, config.field_elements_per_cell as usize, "Cell")
+}
+
+// 将一个拉平的Fr数组按field_elements_per_cell分组，拼接成每个Cell对应的十六进制串
+fn frs_to_cell_hex_groups(frs: &[BlstFr], field_elements_per_cell: usize) -> Vec<String> {
+    frs.chunks(field_elements_per_cell)
+        .map(|chunk| chunk.iter().map(|fr| encode(fr.to_bytes())).collect::<Vec<String>>().concat())
+        .collect()
+}
+
+fn fr_to_hex(fr: &BlstFr) -> String {
+    encode(fr.to_bytes())
 }
 
-fn uint8array_to_blob(bytes: &Uint8Array) -> Result<Blob> {
+fn g1_to_hex(g1: &BlstG1) -> String {
+    encode(g1.to_bytes())
+}
+
+// 将一批cell Fr和proof G1组装成对外返回的RecoveredCells，CPU与CUDA两条路径共用同一份格式化逻辑
+fn build_recovered_cells(cells: &[BlstFr], proofs: &[BlstG1], field_elements_per_cell: usize, include_cells: bool) -> RecoveredCells {
+    RecoveredCells {
+        cells: if include_cells {
+            frs_to_cell_hex_groups(cells, field_elements_per_cell)
+        } else {
+            Vec::new()
+        },
+        proofs: proofs.iter().map(g1_to_hex).collect(),
+    }
+}
+
+fn g1_from_slice(slice: &[u8], label: &str) -> Result<BlstG1> {
+    if slice.len() != BYTES_PER_G1 {
+        return Err(Error::new(Status::InvalidArg, format!(
+            "{}长度错误：需{}字节，实际{}字节", label, BYTES_PER_G1, slice.len()
+        )));
+    }
+    BlstG1::from_bytes(slice).map_err(|e| Error::new(Status::InvalidArg, format!(
+        "{}解析失败：{}", label, e
+    )))
+}
+
+fn uint8array_to_g1(bytes: &Uint8Array, label: &str) -> Result<BlstG1> {
+    g1_from_slice(bytes.as_ref(), label)
+}
+
+fn hex_to_g1(hex_str: &str, label: &str) -> Result<BlstG1> {
+    let bytes = decode(hex_str.trim_start_matches("0x")).map_err(|e| Error::new(Status::InvalidArg, format!(
+        "{}十六进制解码失败：{}", label, e
+    )))?;
+    g1_from_slice(&bytes, label)
+}
+
+// 机读错误码：JS调用方可据此分支处理，而不必对中文提示做字符串匹配
+fn setup_error(code: &str, detail: impl std::fmt::Display) -> Error {
+    Error::new(Status::InvalidArg, format!("[{}] {}", code, detail))
+}
+
+// 将可信设置加载失败归类为机读错误码：按库返回的错误枚举变体分支，而非对渲染后的文案做字符串匹配，
+// 这样即使库的错误提示文案改变，分类也不会悄悄退化成笼统的SETUP_LOAD_FAILED
+fn classify_setup_load_error(err: TrustedSetupError) -> Error {
+    match err {
+        TrustedSetupError::WrongNumberOfG1Points { expected, actual } => setup_error("WRONG_G1_COUNT", format!(
+            "G1点数量错误：需{}个，实际{}个", expected, actual
+        )),
+        TrustedSetupError::WrongNumberOfG2Points { expected, actual } => setup_error("WRONG_G2_COUNT", format!(
+            "G2点数量错误：需{}个，实际{}个", expected, actual
+        )),
+        TrustedSetupError::InvalidPoint(detail) => setup_error("INVALID_CURVE_POINT", detail),
+        TrustedSetupError::NotInLagrangeForm => setup_error("NOT_LAGRANGE_FORM", "G1 Lagrange数据不是Lagrange形式".to_string()),
+        other => setup_error("SETUP_LOAD_FAILED", other),
+    }
+}
+
+fn hex_lines_to_bytes(lines: &[&str], point_size: usize) -> Result<Vec<u8>> {
+    let mut out = Vec::with_capacity(lines.len() * point_size);
+    for line in lines {
+        let bytes = decode(line.trim()).map_err(|e| setup_error(
+            "INVALID_CURVE_POINT", format!("曲线点十六进制解析失败：{}", e)
+        ))?;
+        if bytes.len() != point_size {
+            return Err(setup_error("INVALID_CURVE_POINT", format!(
+                "曲线点字节长度错误：需{}字节，实际{}字节", point_size, bytes.len()
+            )));
+        }
+        out.extend_from_slice(&bytes);
+    }
+    Ok(out)
+}
+
+// 解析标准可信设置文本文件：G1点数量行、G2点数量行各自独立成行（而非同一行空格分隔），
+// 随后依次是G1 monomial、G1 lagrange、G2 monomial三段十六进制点
+fn parse_trusted_setup_file(content: &str) -> Result<(Vec<u8>, Vec<u8>, Vec<u8>)> {
+    const BYTES_PER_G2: usize = 96;
+    let mut lines = content.lines();
+
+    let num_g1: usize = lines.next()
+        .and_then(|s| s.trim().parse().ok())
+        .ok_or_else(|| setup_error("SETUP_FILE_TRUNCATED", "文件缺少G1点数量行".to_string()))?;
+    let num_g2: usize = lines.next()
+        .and_then(|s| s.trim().parse().ok())
+        .ok_or_else(|| setup_error("SETUP_FILE_TRUNCATED", "文件缺少G2点数量行".to_string()))?;
+
+    let g1_monomial_hex: Vec<&str> = lines.by_ref().take(num_g1).collect();
+    if g1_monomial_hex.len() != num_g1 {
+        return Err(setup_error("WRONG_G1_COUNT", format!(
+            "G1 monomial点数量错误：需{}个，实际{}个", num_g1, g1_monomial_hex.len()
+        )));
+    }
+
+    let g1_lagrange_hex: Vec<&str> = lines.by_ref().take(num_g1).collect();
+    if g1_lagrange_hex.len() != num_g1 {
+        return Err(setup_error("WRONG_G1_COUNT", format!(
+            "G1 lagrange点数量错误：需{}个，实际{}个", num_g1, g1_lagrange_hex.len()
+        )));
+    }
+
+    let g2_monomial_hex: Vec<&str> = lines.by_ref().take(num_g2).collect();
+    if g2_monomial_hex.len() != num_g2 {
+        return Err(setup_error("WRONG_G2_COUNT", format!(
+            "G2 monomial点数量错误：需{}个，实际{}个", num_g2, g2_monomial_hex.len()
+        )));
+    }
+
+    let g1_monomial_bytes = hex_lines_to_bytes(&g1_monomial_hex, BYTES_PER_G1)?;
+    let g1_lagrange_bytes = hex_lines_to_bytes(&g1_lagrange_hex, BYTES_PER_G1)?;
+    let g2_monomial_bytes = hex_lines_to_bytes(&g2_monomial_hex, BYTES_PER_G2)?;
+
+    Ok((g1_monomial_bytes, g1_lagrange_bytes, g2_monomial_bytes))
+}
+
+fn uint8array_to_blob(bytes: &Uint8Array, config: &KzgSettingsConfig) -> Result<Blob> {
     let slice = bytes.as_ref();
-    // Blob长度：4096个元素×32字节=131072字节（EIP-4844标准）
-    const BYTES_PER_BLOB: usize = 4096 * 32;
-    if slice.len() != BYTES_PER_BLOB {
+    let bytes_per_blob = config.bytes_per_blob();
+    if slice.len() > bytes_per_blob {
         return Err(Error::new(Status::InvalidArg, format!(
-            "Blob长度错误：需{}字节，实际{}字节", BYTES_PER_BLOB, slice.len()
+            "Blob长度错误：最多{}字节，实际{}字节", bytes_per_blob, slice.len()
         )));
     }
+    // 短数据模式：长度不足一个完整Blob时右侧补零处理，而非拒绝，便于提交任意长度的payload
     let mut blob = unsafe { mem::zeroed::<Blob>() };
-    blob.bytes.copy_from_slice(slice);
+    blob.bytes[..slice.len()].copy_from_slice(slice);
     Ok(blob)
 }
 
+// 校验恢复请求：逐Blob检查cell_indices/cells_bytes长度一致、是否达到半数阈值、索引是否越界或重复
+fn validate_recovery_request(
+    cell_indices: &[Vec<u32>],
+    cells_bytes: &[Vec<Uint8Array>],
+    cells_per_ext_blob: usize,
+) -> Result<()> {
+    if cell_indices.len() != cells_bytes.len() {
+        return Err(Error::new(Status::InvalidArg,
+            "cell_indices与cells_bytes的Blob数量不一致".to_string()));
+    }
+    for (indices, cells) in cell_indices.iter().zip(cells_bytes.iter()) {
+        if indices.len() != cells.len() {
+            return Err(Error::new(Status::InvalidArg,
+                "单个Blob内cell_indices与cells_bytes长度不一致".to_string()));
+        }
+        if indices.len() < cells_per_ext_blob / 2 {
+            return Err(Error::new(Status::InvalidArg, format!(
+                "至少需要{}个Cell才能恢复，实际提供{}个", cells_per_ext_blob / 2, indices.len()
+            )));
+        }
+        let mut seen = HashSet::with_capacity(indices.len());
+        for &idx in indices {
+            if idx as usize >= cells_per_ext_blob {
+                return Err(Error::new(Status::InvalidArg, format!("Cell索引越界：{}", idx)));
+            }
+            if !seen.insert(idx) {
+                return Err(Error::new(Status::InvalidArg, format!("Cell索引重复：{}", idx)));
+            }
+        }
+    }
+    Ok(())
+}
+
+// 校验commitments、cell_indices、cells、proofs四个批量参数的长度是否一一对应
+fn validate_verify_cell_proofs_lengths(
+    commitments_len: usize,
+    cell_indices_len: usize,
+    cells_len: usize,
+    proofs_len: usize,
+) -> Result<()> {
+    if cell_indices_len != commitments_len || cells_len != commitments_len || proofs_len != commitments_len {
+        return Err(Error::new(Status::InvalidArg,
+            "commitments、cell_indices、cells、proofs长度必须相等".to_string()));
+    }
+    Ok(())
+}
+
 // -------------- 核心结构体 --------------
+#[napi(object)]
+pub struct RecoveredCells {
+    pub cells: Vec<String>,
+    pub proofs: Vec<String>,
+}
+
+#[napi(object)]
+pub struct BackendInfo {
+    pub gpu_active: bool,
+    pub device: Option<String>,
+    // 最近一次CUDA运行时失败的诊断信息：区分"本就没有GPU"与"GPU有但跑挂了回退CPU"，
+    // 供Node宿主按需查询/上报，而非由库直接往stderr打印一条它无法配置或抑制的调试日志
+    pub last_cuda_error: Option<String>,
+}
+
 #[napi]
 pub struct KzgWrapper {
     settings: FsKZGSettings,
+    config: KzgSettingsConfig,
+    cuda_last_error: Arc<Mutex<Option<String>>>,
 }
 
 #[napi]
@@ -61,81 +346,127 @@ impl KzgWrapper {
         g1_monomial_bytes: Uint8Array,
         g1_lagrange_bytes: Uint8Array,
         g2_monomial_bytes: Uint8Array,
+        config: Option<KzgSettingsConfig>,
     ) -> Result<Self> {
         // 验证G1/G2字节长度（使用手动定义的常量）
         if g1_monomial_bytes.len() % BYTES_PER_G1 != 0 {
-            return Err(Error::new(Status::InvalidArg, format!(
+            return Err(setup_error("WRONG_G1_COUNT", format!(
                 "G1字节长度必须是{}的倍数", BYTES_PER_G1
             )));
         }
         const BYTES_PER_G2: usize = 96;  // G2点固定96字节
         if g2_monomial_bytes.len() % BYTES_PER_G2 != 0 {
-            return Err(Error::new(Status::InvalidArg, format!(
+            return Err(setup_error("WRONG_G2_COUNT", format!(
                 "G2字节长度必须是{}的倍数", BYTES_PER_G2
             )));
         }
 
+        let field_elements_per_blob_actual = derive_field_elements_per_blob(g1_monomial_bytes.as_ref());
+
         let settings = load_trusted_setup_rust(
             g1_monomial_bytes.as_ref(),
             g1_lagrange_bytes.as_ref(),
             g2_monomial_bytes.as_ref(),
-        ).map_err(|e| Error::new(Status::GenericFailure, format!(
-            "加载可信设置失败：{}", e
-        )))?;
+        ).map_err(classify_setup_load_error)?;
+
+        let config = resolve_config(config, field_elements_per_blob_actual)?;
+
+        Ok(Self { settings, config, cuda_last_error: Arc::new(Mutex::new(None)) })
+    }
+
+    // 从标准可信设置文本文件加载（G1点数量行 + G2点数量行 + G1 monomial + G1 lagrange + G2 monomial十六进制行）
+    #[napi(factory)]
+    pub fn load_trusted_setup_file(path: String, config: Option<KzgSettingsConfig>) -> Result<Self> {
+        let content = std::fs::read_to_string(&path).map_err(|e| setup_error(
+            "SETUP_FILE_IO_ERROR", format!("无法读取可信设置文件 {}：{}", path, e)
+        ))?;
 
-        Ok(Self { settings })
+        let (g1_monomial_bytes, g1_lagrange_bytes, g2_monomial_bytes) = parse_trusted_setup_file(&content)?;
+
+        let field_elements_per_blob_actual = derive_field_elements_per_blob(&g1_monomial_bytes);
+
+        let settings = load_trusted_setup_rust(
+            &g1_monomial_bytes,
+            &g1_lagrange_bytes,
+            &g2_monomial_bytes,
+        ).map_err(classify_setup_load_error)?;
+
+        let config = resolve_config(config, field_elements_per_blob_actual)?;
+
+        Ok(Self { settings, config, cuda_last_error: Arc::new(Mutex::new(None)) })
     }
 
     #[napi]
-    pub async fn compute_cell_proofs_batch(&self, blobs_bytes: Vec<Uint8Array>) -> Result<Vec<Vec<String>>> {
+    pub async fn compute_cell_proofs_batch(
+        &self,
+        blobs_bytes: Vec<Uint8Array>,
+        include_cells: Option<bool>,
+    ) -> Result<Vec<RecoveredCells>> {
         let rust_settings = self.settings.clone();
+        let rust_config = self.config;
+        let include_cells = include_cells.unwrap_or(false);
+        #[cfg(feature = "cuda")]
+        let cuda_last_error = self.cuda_last_error.clone();
 
         let handle = task::spawn_blocking(move || {
             let blobs: Result<Vec<Blob>> = blobs_bytes.into_iter()
-                .map(|bytes| uint8array_to_blob(&bytes))
+                .map(|bytes| uint8array_to_blob(&bytes, &rust_config))
                 .collect();
             let blobs = blobs?;
 
-            let results: Vec<Vec<String>> = blobs.par_iter()
-                .map(|blob| {
-                    // 初始化cells（避免Clone，用with_capacity+push）
-                    let mut cells = Vec::with_capacity(CELLS_PER_EXT_BLOB);
-                    for _ in 0..CELLS_PER_EXT_BLOB {
-                        cells.push(unsafe { mem::zeroed::<Cell>() });
-                    }
+            let field_elements_per_blob = rust_config.field_elements_per_blob as usize;
+            let field_elements_per_cell = rust_config.field_elements_per_cell as usize;
+            let cells_per_ext_blob = rust_config.cells_per_ext_blob();
+
+            let blobs_frs: Result<Vec<Vec<BlstFr>>> = blobs.par_iter()
+                .map(|blob| blob_bytes_to_frs(blob, field_elements_per_blob))
+                .collect();
+            let blobs_frs = blobs_frs?;
 
-                    // 初始化proofs（同上）
-                    let mut proofs = Vec::with_capacity(CELLS_PER_EXT_BLOB);
-                    for _ in 0..CELLS_PER_EXT_BLOB {
-                        proofs.push(unsafe { mem::zeroed::<KZGProof>() });
+            #[cfg(feature = "cuda")]
+            if cuda_device_name().is_some() {
+                // GPU一次launch处理整批Blob的MSM，摊销host<->device传输开销，而非逐Blob调用；
+                // 适配器返回与CPU路径同层的Fr/G1结果，而非napi专属的RecoveredCells——
+                // 这是kzg_traits层面的通用计算结果，外部CUDA后端不应该知道napi绑定的存在
+                match compute_cells_and_kzg_proofs_cuda(
+                    &rust_settings,
+                    &blobs_frs,
+                    cells_per_ext_blob,
+                    field_elements_per_cell,
+                ) {
+                    Ok(raw_results) => {
+                        return Ok(raw_results.iter()
+                            .map(|(cells, proofs)| build_recovered_cells(cells, proofs, field_elements_per_cell, include_cells))
+                            .collect());
                     }
+                    // GPU运行时失败（如显存不足、驱动错误）时回退到下方CPU路径，而非直接中断整批请求；
+                    // 但失败本身必须留痕——记录到last_cuda_error供backend_info()查询，而非直接打到stderr，
+                    // 这样Node宿主可以按需读取/上报，而不是被一条它既不能配置也不能抑制的调试日志打扰
+                    Err(e) => {
+                        *cuda_last_error.lock().unwrap() = Some(e.to_string());
+                    }
+                }
+            }
+
+            // CPU路径：未启用cuda特性或运行时无可用GPU设备时，逐Blob并行计算
+            let results: Vec<RecoveredCells> = blobs_frs.par_iter()
+                .map(|blob_frs| {
+                    let mut cells = vec![BlstFr::default(); cells_per_ext_blob * field_elements_per_cell];
+                    let mut proofs = vec![BlstG1::default(); cells_per_ext_blob];
 
-                    // 转换设置：直接使用rust_kzg_blst的C绑定加载方法（绕开私有函数）
-                    // 注意：这里假设settings内部已包含CKZGSettings的指针，或通过其他公开方法获取
-                    // 若仍有问题，可改用rust_kzg_blst::eip_4844::load_trusted_setup生成CKZGSettings
-                    let c_settings = unsafe {
-                        // 临时方案：将FsKZGSettings转为*const CKZGSettings（需确保内部结构兼容）
-                        &rust_settings as *const FsKZGSettings as *const CKZGSettings
-                    };
-
-                    // 调用C绑定函数
-                    let ret = unsafe {
-                        compute_cells_and_kzg_proofs(
-                            cells.as_mut_ptr(),
-                            proofs.as_mut_ptr(),
-                            blob as *const Blob,
-                            c_settings,
-                        )
-                    };
-
-                    check_c_kzg_ret(ret, "生成Cell Proofs")?;
-
-                    // 释放设置（如果有必要）
-                    // unsafe { rust_kzg_blst::eip_4844::free_trusted_setup(c_settings as *mut CKZGSettings) };
-
-                    Ok(proofs.iter().map(proof_to_hex).collect())
+                    // 经由安全的DAS trait调用，代替FsKZGSettings到CKZGSettings的裸指针转换
+                    <FsKZGSettings as DAS<BlstBackend>>::compute_cells_and_kzg_proofs(
+                        &rust_settings,
+                        Some(&mut cells),
+                        Some(&mut proofs),
+                        blob_frs,
+                    ).map_err(|e| Error::new(Status::GenericFailure, format!(
+                        "生成Cell Proofs失败：{}", e
+                    )))?;
+
+                    Ok(build_recovered_cells(&cells, &proofs, field_elements_per_cell, include_cells))
                 })
-                .collect::<Result<Vec<Vec<String>>>>()?;
+                .collect::<Result<Vec<RecoveredCells>>>()?;
 
             Ok(results)
         });
@@ -144,4 +475,428 @@ impl KzgWrapper {
             "异步任务失败：{}", e
         )))?
     }
+
+    // 确认GPU加速是否生效，部署方可据此验证快速路径是否被实际启用
+    #[napi]
+    pub fn backend_info(&self) -> BackendInfo {
+        #[cfg(feature = "cuda")]
+        let last_cuda_error = self.cuda_last_error.lock().unwrap().clone();
+        #[cfg(not(feature = "cuda"))]
+        let last_cuda_error = None;
+
+        #[cfg(feature = "cuda")]
+        {
+            if let Some(device) = cuda_device_name() {
+                return BackendInfo { gpu_active: true, device: Some(device), last_cuda_error };
+            }
+        }
+        BackendInfo { gpu_active: false, device: None, last_cuda_error }
+    }
+
+    // DAS采样节点的反向操作：从部分下载的Cell恢复完整Cell集合及其KZG证明
+    #[napi]
+    pub async fn recover_cells_and_kzg_proofs(
+        &self,
+        cell_indices: Vec<Vec<u32>>,
+        cells_bytes: Vec<Vec<Uint8Array>>,
+    ) -> Result<Vec<RecoveredCells>> {
+        let cells_per_ext_blob = self.config.cells_per_ext_blob();
+        validate_recovery_request(&cell_indices, &cells_bytes, cells_per_ext_blob)?;
+
+        let rust_settings = self.settings.clone();
+        let rust_config = self.config;
+
+        let handle = task::spawn_blocking(move || {
+            // 每个Blob内按cell_indices顺序拼接提供的Cell，形成一个拉平的Fr数组
+            let per_blob: Vec<(Vec<usize>, Vec<BlstFr>)> = cell_indices.into_iter()
+                .zip(cells_bytes.into_iter())
+                .map(|(indices, cells)| {
+                    let cells_frs: Result<Vec<BlstFr>> = cells.iter()
+                        .map(|c| uint8array_to_cell_frs(c, &rust_config))
+                        .collect::<Result<Vec<Vec<BlstFr>>>>()
+                        .map(|groups| groups.into_iter().flatten().collect());
+                    Ok((indices.into_iter().map(|i| i as usize).collect(), cells_frs?))
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            let field_elements_per_cell = rust_config.field_elements_per_cell as usize;
+            let cells_per_ext_blob = rust_config.cells_per_ext_blob();
+
+            let results: Vec<RecoveredCells> = per_blob.par_iter()
+                .map(|(indices, cells_frs)| {
+                    let mut recovered_cells = vec![BlstFr::default(); cells_per_ext_blob * field_elements_per_cell];
+                    let mut recovered_proofs = vec![BlstG1::default(); cells_per_ext_blob];
+
+                    // 经由安全的DAS trait调用，代替FsKZGSettings到CKZGSettings的裸指针转换；
+                    // 对扩展域上的多项式做Reed-Solomon插值重建：任意半数Cell即可唯一确定多项式
+                    <FsKZGSettings as DAS<BlstBackend>>::recover_cells_and_kzg_proofs(
+                        &rust_settings,
+                        Some(&mut recovered_cells),
+                        Some(&mut recovered_proofs),
+                        indices,
+                        cells_frs,
+                    ).map_err(|e| Error::new(Status::GenericFailure, format!(
+                        "恢复Cell与Proofs失败：{}", e
+                    )))?;
+
+                    Ok(RecoveredCells {
+                        cells: frs_to_cell_hex_groups(&recovered_cells, field_elements_per_cell),
+                        proofs: recovered_proofs.iter().map(g1_to_hex).collect(),
+                    })
+                })
+                .collect::<Result<Vec<RecoveredCells>>>()?;
+
+            Ok(results)
+        });
+
+        handle.await.map_err(|e| Error::new(Status::GenericFailure, format!(
+            "异步任务失败：{}", e
+        )))?
+    }
+
+    // 批量验证(commitment, cell_index, cell, proof)四元组，供DAS采样和区块校验使用
+    #[napi]
+    pub async fn verify_cell_proofs_batch(
+        &self,
+        commitments: Vec<Uint8Array>,
+        cell_indices: Vec<u32>,
+        cells: Vec<Uint8Array>,
+        proofs: Vec<Uint8Array>,
+    ) -> Result<bool> {
+        validate_verify_cell_proofs_lengths(commitments.len(), cell_indices.len(), cells.len(), proofs.len())?;
+
+        let rust_settings = self.settings.clone();
+        let rust_config = self.config;
+
+        let handle = task::spawn_blocking(move || {
+            let commitments_g1: Result<Vec<BlstG1>> = commitments.iter()
+                .map(|c| uint8array_to_g1(c, "Commitment"))
+                .collect();
+            let commitments_g1 = commitments_g1?;
+
+            let cells_frs: Result<Vec<BlstFr>> = cells.iter()
+                .map(|c| uint8array_to_cell_frs(c, &rust_config))
+                .collect::<Result<Vec<Vec<BlstFr>>>>()
+                .map(|groups| groups.into_iter().flatten().collect());
+            let cells_frs = cells_frs?;
+
+            let proofs_g1: Result<Vec<BlstG1>> = proofs.iter()
+                .map(|p| uint8array_to_g1(p, "Proof"))
+                .collect();
+            let proofs_g1 = proofs_g1?;
+
+            let indices_usize: Vec<usize> = cell_indices.iter().map(|&i| i as usize).collect();
+
+            // 经由安全的DAS trait调用，代替FsKZGSettings到CKZGSettings的裸指针转换；
+            // 将N组独立的pairing校验合并为一次随机线性组合多重pairing，成本接近O(1)次而非O(N)次
+            let ok = <FsKZGSettings as DAS<BlstBackend>>::verify_cell_kzg_proof_batch(
+                &rust_settings,
+                &commitments_g1,
+                &indices_usize,
+                &cells_frs,
+                &proofs_g1,
+            ).map_err(|e| Error::new(Status::GenericFailure, format!(
+                "批量验证Cell Proofs失败：{}", e
+            )))?;
+
+            Ok(ok)
+        });
+
+        handle.await.map_err(|e| Error::new(Status::GenericFailure, format!(
+            "异步任务失败：{}", e
+        )))?
+    }
+
+    // EIP-4844路径：为一批Blob生成KZG承诺（48字节，十六进制编码）
+    #[napi]
+    pub async fn compute_blob_commitments_batch(&self, blobs_bytes: Vec<Uint8Array>) -> Result<Vec<String>> {
+        let rust_settings = self.settings.clone();
+        let rust_config = self.config;
+
+        let handle = task::spawn_blocking(move || {
+            let field_elements_per_blob = rust_config.field_elements_per_blob as usize;
+
+            let blobs_frs: Result<Vec<Vec<BlstFr>>> = blobs_bytes.iter()
+                .map(|bytes| uint8array_to_blob(bytes, &rust_config))
+                .collect::<Result<Vec<Blob>>>()?
+                .par_iter()
+                .map(|blob| blob_bytes_to_frs(blob, field_elements_per_blob))
+                .collect();
+            let blobs_frs = blobs_frs?;
+
+            let results: Vec<String> = blobs_frs.par_iter()
+                .map(|blob_frs| {
+                    // 经由安全的Kzg4844 trait调用，代替FsKZGSettings到CKZGSettings的裸指针转换
+                    let commitment = <FsKZGSettings as Kzg4844<BlstBackend>>::blob_to_kzg_commitment(
+                        &rust_settings, blob_frs,
+                    ).map_err(|e| Error::new(Status::GenericFailure, format!(
+                        "生成Blob承诺失败：{}", e
+                    )))?;
+                    Ok(g1_to_hex(&commitment))
+                })
+                .collect::<Result<Vec<String>>>()?;
+
+            Ok(results)
+        });
+
+        handle.await.map_err(|e| Error::new(Status::GenericFailure, format!(
+            "异步任务失败：{}", e
+        )))?
+    }
+
+    // EIP-4844路径：为一批(Blob, 承诺)生成经典Blob KZG证明
+    #[napi]
+    pub async fn compute_blob_kzg_proofs_batch(
+        &self,
+        blobs_bytes: Vec<Uint8Array>,
+        commitments: Vec<String>,
+    ) -> Result<Vec<String>> {
+        if blobs_bytes.len() != commitments.len() {
+            return Err(Error::new(Status::InvalidArg,
+                "blobs_bytes与commitments长度必须相等".to_string()));
+        }
+
+        let rust_settings = self.settings.clone();
+        let rust_config = self.config;
+
+        let handle = task::spawn_blocking(move || {
+            let field_elements_per_blob = rust_config.field_elements_per_blob as usize;
+
+            let blobs_frs: Result<Vec<Vec<BlstFr>>> = blobs_bytes.iter()
+                .map(|bytes| uint8array_to_blob(bytes, &rust_config))
+                .collect::<Result<Vec<Blob>>>()?
+                .par_iter()
+                .map(|blob| blob_bytes_to_frs(blob, field_elements_per_blob))
+                .collect();
+            let blobs_frs = blobs_frs?;
+
+            let commitments_g1: Result<Vec<BlstG1>> = commitments.iter()
+                .map(|c| hex_to_g1(c, "Commitment"))
+                .collect();
+            let commitments_g1 = commitments_g1?;
+
+            let results: Vec<String> = blobs_frs.par_iter().zip(commitments_g1.par_iter())
+                .map(|(blob_frs, commitment_g1)| {
+                    // 经由安全的Kzg4844 trait调用，代替FsKZGSettings到CKZGSettings的裸指针转换
+                    let proof = <FsKZGSettings as Kzg4844<BlstBackend>>::compute_blob_kzg_proof(
+                        &rust_settings, blob_frs, commitment_g1,
+                    ).map_err(|e| Error::new(Status::GenericFailure, format!(
+                        "生成Blob KZG证明失败：{}", e
+                    )))?;
+                    Ok(g1_to_hex(&proof))
+                })
+                .collect::<Result<Vec<String>>>()?;
+
+            Ok(results)
+        });
+
+        handle.await.map_err(|e| Error::new(Status::GenericFailure, format!(
+            "异步任务失败：{}", e
+        )))?
+    }
+
+    // EIP-4844路径：批量验证经典Blob KZG证明，多个Blob折叠为一次随机线性组合多重pairing
+    #[napi]
+    pub async fn verify_blob_kzg_proof_batch(
+        &self,
+        blobs_bytes: Vec<Uint8Array>,
+        commitments: Vec<String>,
+        proofs: Vec<String>,
+    ) -> Result<bool> {
+        let len = blobs_bytes.len();
+        if commitments.len() != len || proofs.len() != len {
+            return Err(Error::new(Status::InvalidArg,
+                "blobs_bytes、commitments、proofs长度必须相等".to_string()));
+        }
+
+        let rust_settings = self.settings.clone();
+        let rust_config = self.config;
+
+        let handle = task::spawn_blocking(move || {
+            let field_elements_per_blob = rust_config.field_elements_per_blob as usize;
+
+            let blobs_frs: Result<Vec<Vec<BlstFr>>> = blobs_bytes.iter()
+                .map(|bytes| uint8array_to_blob(bytes, &rust_config))
+                .collect::<Result<Vec<Blob>>>()?
+                .par_iter()
+                .map(|blob| blob_bytes_to_frs(blob, field_elements_per_blob))
+                .collect();
+            let blobs_frs = blobs_frs?;
+
+            let commitments_g1: Result<Vec<BlstG1>> = commitments.iter()
+                .map(|c| hex_to_g1(c, "Commitment"))
+                .collect();
+            let commitments_g1 = commitments_g1?;
+
+            let proofs_g1: Result<Vec<BlstG1>> = proofs.iter()
+                .map(|p| hex_to_g1(p, "Proof"))
+                .collect();
+            let proofs_g1 = proofs_g1?;
+
+            // 经由安全的Kzg4844 trait调用，代替FsKZGSettings到CKZGSettings的裸指针转换
+            let ok = <FsKZGSettings as Kzg4844<BlstBackend>>::verify_blob_kzg_proof_batch(
+                &rust_settings, &blobs_frs, &commitments_g1, &proofs_g1,
+            ).map_err(|e| Error::new(Status::GenericFailure, format!(
+                "批量验证Blob KZG证明失败：{}", e
+            )))?;
+
+            Ok(ok)
+        });
+
+        handle.await.map_err(|e| Error::new(Status::GenericFailure, format!(
+            "异步任务失败：{}", e
+        )))?
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(len: usize) -> Uint8Array {
+        Uint8Array::from(vec![0u8; len])
+    }
+
+    #[test]
+    fn recovery_rejects_insufficient_cells() {
+        let cells_per_ext_blob = 32;
+        let indices = vec![vec![0u32, 1, 2]];
+        let cells = vec![vec![cell(1), cell(1), cell(1)]];
+        let err = validate_recovery_request(&indices, &cells, cells_per_ext_blob).unwrap_err();
+        assert!(err.reason.contains("至少需要"));
+    }
+
+    #[test]
+    fn recovery_rejects_duplicate_indices() {
+        let cells_per_ext_blob = 4;
+        let indices = vec![vec![0u32, 0u32]];
+        let cells = vec![vec![cell(1), cell(1)]];
+        let err = validate_recovery_request(&indices, &cells, cells_per_ext_blob).unwrap_err();
+        assert!(err.reason.contains("重复"));
+    }
+
+    #[test]
+    fn recovery_rejects_out_of_range_indices() {
+        let cells_per_ext_blob = 4;
+        let indices = vec![vec![0u32, 10u32]];
+        let cells = vec![vec![cell(1), cell(1)]];
+        let err = validate_recovery_request(&indices, &cells, cells_per_ext_blob).unwrap_err();
+        assert!(err.reason.contains("越界"));
+    }
+
+    #[test]
+    fn recovery_rejects_mismatched_batch_lengths() {
+        let cells_per_ext_blob = 4;
+        let indices = vec![vec![0u32, 1u32], vec![0u32, 1u32]];
+        let cells = vec![vec![cell(1), cell(1)]];
+        let err = validate_recovery_request(&indices, &cells, cells_per_ext_blob).unwrap_err();
+        assert!(err.reason.contains("Blob数量不一致"));
+    }
+
+    #[test]
+    fn recovery_rejects_mismatched_indices_and_cells_within_blob() {
+        let cells_per_ext_blob = 4;
+        let indices = vec![vec![0u32, 1u32]];
+        let cells = vec![vec![cell(1)]];
+        let err = validate_recovery_request(&indices, &cells, cells_per_ext_blob).unwrap_err();
+        assert!(err.reason.contains("长度不一致"));
+    }
+
+    #[test]
+    fn verify_cell_proofs_accepts_matching_lengths() {
+        assert!(validate_verify_cell_proofs_lengths(2, 2, 2, 2).is_ok());
+    }
+
+    #[test]
+    fn verify_cell_proofs_rejects_mismatched_cell_indices_length() {
+        let err = validate_verify_cell_proofs_lengths(2, 3, 2, 2).unwrap_err();
+        assert!(err.reason.contains("长度必须相等"));
+    }
+
+    #[test]
+    fn verify_cell_proofs_rejects_mismatched_cells_length() {
+        let err = validate_verify_cell_proofs_lengths(2, 2, 1, 2).unwrap_err();
+        assert!(err.reason.contains("长度必须相等"));
+    }
+
+    #[test]
+    fn verify_cell_proofs_rejects_mismatched_proofs_length() {
+        let err = validate_verify_cell_proofs_lengths(2, 2, 2, 1).unwrap_err();
+        assert!(err.reason.contains("长度必须相等"));
+    }
+
+    #[test]
+    fn uint8array_to_blob_zero_pads_short_data() {
+        let config = KzgSettingsConfig::default();
+        let payload = vec![1u8, 2, 3];
+        let blob = uint8array_to_blob(&Uint8Array::from(payload.clone()), &config).unwrap();
+        assert_eq!(&blob.bytes[..3], &payload[..]);
+        assert!(blob.bytes[3..].iter().all(|&b| b == 0));
+    }
+
+    #[test]
+    fn uint8array_to_blob_rejects_oversized_data() {
+        let config = KzgSettingsConfig::default();
+        let payload = vec![0u8; config.bytes_per_blob() + 1];
+        let err = uint8array_to_blob(&Uint8Array::from(payload), &config).unwrap_err();
+        assert!(err.reason.contains("长度错误"));
+    }
+
+    #[test]
+    fn default_config_satisfies_its_own_domain_invariant() {
+        let config = KzgSettingsConfig::default();
+        assert_eq!(
+            config.cells_per_ext_blob as usize * config.field_elements_per_cell as usize,
+            2 * config.field_elements_per_blob as usize,
+        );
+    }
+
+    // 端到端覆盖load_trusted_setup(_file)在config=None时走的真实路径：
+    // 用标准域大小的G1 monomial字节长度推导field_elements_per_blob，再交给resolve_config派生其余维度
+    #[test]
+    fn resolve_config_none_branch_derives_standard_domain_end_to_end() {
+        let g1_monomial_bytes = vec![0u8; 4096 * BYTES_PER_G1];
+        let field_elements_per_blob_actual = derive_field_elements_per_blob(&g1_monomial_bytes);
+        assert_eq!(field_elements_per_blob_actual, 4096);
+
+        let config = resolve_config(None, field_elements_per_blob_actual).unwrap();
+        assert_eq!(config.field_elements_per_blob, 4096);
+        assert_eq!(config.field_elements_per_cell, 64);
+        assert_eq!(config.cells_per_ext_blob, 128);
+
+        // 派生出的config必须能通过自身的一致性校验（Some分支），而不仅仅是"看起来正确"
+        assert!(resolve_config(Some(config), field_elements_per_blob_actual).is_ok());
+    }
+
+    fn hex_point(byte: u8, size: usize) -> String {
+        encode(vec![byte; size])
+    }
+
+    #[test]
+    fn parse_trusted_setup_file_reads_counts_from_separate_lines() {
+        let g1 = hex_point(1, BYTES_PER_G1);
+        let g1_lagrange = hex_point(2, BYTES_PER_G1);
+        let g2 = hex_point(3, 96);
+        let content = format!("1\n1\n{}\n{}\n{}\n", g1, g1_lagrange, g2);
+
+        let (g1_monomial_bytes, g1_lagrange_bytes, g2_monomial_bytes) = parse_trusted_setup_file(&content).unwrap();
+        assert_eq!(g1_monomial_bytes, vec![1u8; BYTES_PER_G1]);
+        assert_eq!(g1_lagrange_bytes, vec![2u8; BYTES_PER_G1]);
+        assert_eq!(g2_monomial_bytes, vec![3u8; 96]);
+    }
+
+    #[test]
+    fn parse_trusted_setup_file_rejects_counts_sharing_one_line() {
+        // 旧实现会把"1 1"当成一行计数行解析，新实现要求两行各自独立，因此这种格式必须被拒绝
+        let content = "1 1\n".to_string();
+        let err = parse_trusted_setup_file(&content).unwrap_err();
+        assert!(err.reason.contains("SETUP_FILE_TRUNCATED"));
+    }
+
+    #[test]
+    fn parse_trusted_setup_file_rejects_missing_g2_count_line() {
+        let content = "1\n".to_string();
+        let err = parse_trusted_setup_file(&content).unwrap_err();
+        assert!(err.reason.contains("G2点数量行"));
+    }
 }